@@ -1,14 +1,26 @@
 // Import necessary crates and modules
 use borsh::{BorshDeserialize, BorshSerialize};
-use ethers::abi::{decode, ParamType};
-use ethers::types::Log;
-use l1x_sdk::{store::LookupMap, storage_read, storage_write};
+use ethers::abi::{decode, encode, ParamType, Token};
+use ethers::types::{Log, H256};
+// `storage_read`/`storage_write`/`LookupMap` all bottom out in `l1x_sdk`'s
+// host FFI, which only resolves on the on-chain wasm32 runtime. Gating the
+// import keeps those symbols out of test builds entirely — see `EventsMap`
+// and friends below for the `cfg(test)` replacement.
+#[cfg(not(test))]
+use l1x_sdk::{storage_read, storage_write, store::LookupMap};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::convert::TryFrom;
+use std::fmt;
 
 // Define constants for storage keys
 const STORAGE_CONTRACT_KEY: &[u8; 7] = b"message";
+#[cfg(not(test))]
 const STORAGE_EVENTS_KEY: &[u8; 6] = b"events";
+#[cfg(not(test))]
+const STORAGE_NONCES_KEY: &[u8; 6] = b"nonces";
+#[cfg(not(test))]
+const STORAGE_PAYLOADS_KEY: &[u8; 8] = b"payloads";
 
 // Define data structures for messages
 #[derive(Clone, Debug, BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
@@ -39,6 +51,163 @@ pub struct XTalkMessageInitiated {
     destination_smart_contract_address: [u8; 32],
 }
 
+// Decoded representation of every event this contract knows how to ingest.
+// New cross-chain message shapes are added here and in `event_registry`,
+// not at the `save_event_data` call site.
+#[derive(Clone, Debug)]
+pub enum DecodedEvent {
+    SendMessage(XCDPSendMessage),
+}
+
+// Errors that can occur while turning a raw `Log` into a `DecodedEvent`.
+#[derive(Debug)]
+pub enum EventDecodeError {
+    MissingTopic0,
+    UnknownSignature(H256),
+    AbiDecode(ethers::abi::Error),
+    UnexpectedTokenShape,
+}
+
+impl fmt::Display for EventDecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EventDecodeError::MissingTopic0 => write!(
+                f,
+                "log has no topics, expected topics[0] to carry an event signature"
+            ),
+            EventDecodeError::UnknownSignature(topic0) => {
+                write!(
+                    f,
+                    "log topics[0] ({:#x}) does not match any known event signature",
+                    topic0
+                )
+            }
+            EventDecodeError::AbiDecode(err) => write!(f, "ABI decode failed: {}", err),
+            EventDecodeError::UnexpectedTokenShape => {
+                write!(f, "decoded tokens did not match the expected event shape")
+            }
+        }
+    }
+}
+
+impl TryFrom<Vec<Token>> for XCDPSendMessageSolidity {
+    type Error = EventDecodeError;
+
+    fn try_from(tokens: Vec<Token>) -> Result<Self, Self::Error> {
+        let message = tokens
+            .into_iter()
+            .next()
+            .and_then(Token::into_string)
+            .ok_or(EventDecodeError::UnexpectedTokenShape)?;
+        Ok(Self { message })
+    }
+}
+
+// One entry in the event-descriptor registry: the event's ABI parameter
+// types (for `decode`) and a constructor turning the decoded tokens into a
+// `DecodedEvent`.
+struct EventDescriptor {
+    param_types: Vec<ParamType>,
+    to_event: fn(Vec<Token>) -> Result<DecodedEvent, EventDecodeError>,
+}
+
+// Compile-time-known `keccak256("XCDPSendMessage(string)")`, i.e. the exact
+// `log.topics[0]` the EVM emits for this event. Hardcoding the hash (rather
+// than calling `keccak256` on the signature string at registry-build time)
+// gives an explicit, auditable value to check an incoming log's signature
+// against before anything is decoded.
+const XCDP_SEND_MESSAGE_TOPIC0: [u8; 32] = [
+    0x52, 0x83, 0x38, 0x29, 0xd8, 0xd1, 0x3d, 0xa2, 0xe6, 0x88, 0x7e, 0xcb, 0xa6, 0x6d, 0xa1, 0xfd,
+    0xe1, 0x04, 0x70, 0x52, 0xb9, 0xa0, 0x6d, 0xc5, 0x89, 0x48, 0xe7, 0xe1, 0xe3, 0xc8, 0xff, 0xff,
+];
+
+// Cross-chain events this contract knows how to ingest, keyed by the
+// event's signature hash, i.e. `log.topics[0]`. Supporting a new event shape
+// means adding an entry here, not touching `save_event_data`. Because
+// lookups are by exact signature hash, a log whose `topics[0]` doesn't match
+// one of these is rejected before `decode` ever runs on its data.
+fn event_registry() -> HashMap<H256, EventDescriptor> {
+    let mut registry = HashMap::new();
+    registry.insert(
+        H256::from(XCDP_SEND_MESSAGE_TOPIC0),
+        EventDescriptor {
+            param_types: vec![ParamType::String],
+            to_event: |tokens| {
+                XCDPSendMessageSolidity::try_from(tokens)
+                    .map(|event| DecodedEvent::SendMessage(event.into()))
+            },
+        },
+    );
+    registry
+}
+
+// Verifies `log.topics[0]` against the event registry before decoding
+// `log.data`, and looks up the matching `DecodedEvent`. Guards against two
+// kinds of malformed input: a log with no topics at all (would otherwise
+// panic on `topics[0]`), and a log whose topic0 doesn't match any known
+// event signature (would otherwise be decoded as whatever shape happened to
+// fit, e.g. a spoofed log accepted as `XCDPSendMessage`).
+fn decode_event(log: &Log) -> Result<(H256, DecodedEvent), EventDecodeError> {
+    let topic0 = *log.topics.first().ok_or(EventDecodeError::MissingTopic0)?;
+    let descriptor = event_registry()
+        .remove(&topic0)
+        .ok_or(EventDecodeError::UnknownSignature(topic0))?;
+    let tokens =
+        decode(&descriptor.param_types, &log.data.0).map_err(EventDecodeError::AbiDecode)?;
+    let event = (descriptor.to_event)(tokens)?;
+    Ok((topic0, event))
+}
+
+// Abstracts the key/value storage the top-level contract blob is persisted
+// to. Combined with swapping `LookupMap` for a plain `HashMap` under
+// `cfg(test)` (see `EventsMap`/`NoncesMap`/`PayloadsMap` below), this lets
+// the full business logic in `XCDPCore` (migration, decoding, dedup, key
+// composition, and the event/nonce/payload maps themselves) run in a plain
+// `cargo test` instead of requiring the on-chain runtime — the only place
+// `l1x_sdk`'s storage host functions resolve.
+pub trait StorageIO {
+    fn read(&self, key: &[u8]) -> Option<Vec<u8>>;
+    fn write(&mut self, key: &[u8], value: &[u8]);
+}
+
+// Production `StorageIO`, delegating straight to the L1X runtime. The
+// `storage_read`/`storage_write` calls are gated out under `cfg(test)`
+// rather than just unused, since referencing them at all pulls in `l1x_sdk`
+// host FFI symbols that don't resolve off-chain — tests never construct an
+// `L1xStorageIO`, so the bodies are unreachable there.
+pub struct L1xStorageIO;
+
+impl StorageIO for L1xStorageIO {
+    #[cfg(not(test))]
+    fn read(&self, key: &[u8]) -> Option<Vec<u8>> {
+        storage_read(key)
+    }
+
+    #[cfg(test)]
+    fn read(&self, _key: &[u8]) -> Option<Vec<u8>> {
+        unreachable!("L1xStorageIO is production-only; tests use InMemoryStorageIO")
+    }
+
+    #[cfg(not(test))]
+    fn write(&mut self, key: &[u8], value: &[u8]) {
+        storage_write(key, value);
+    }
+
+    #[cfg(test)]
+    fn write(&mut self, _key: &[u8], _value: &[u8]) {
+        unreachable!("L1xStorageIO is production-only; tests use InMemoryStorageIO")
+    }
+}
+
+// Decodes base64-encoded raw event bytes into a `Log`. Pulled out as a pure
+// function, independent of contract storage, so it can be exercised in a
+// plain `cargo test` against crafted base64/JSON blobs.
+fn decode_log_bytes(event_data: &[u8]) -> Result<Log, String> {
+    let event_data =
+        base64::decode(event_data).map_err(|_| "Can't decode base64 event_data".to_string())?;
+    serde_json::from_slice(&event_data).map_err(|_| "Can't deserialize Log object".to_string())
+}
+
 // Payload structure for inter-chain messages
 #[derive(Clone, Debug, BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
 pub struct Payload {
@@ -47,40 +216,208 @@ pub struct Payload {
     destination_contract_address: [u8; 32],
 }
 
-// Main contract structure storing all event data
+// Backing type for each of the maps below: `LookupMap` on-chain, a plain
+// `HashMap` under `cfg(test)`. Both expose the same `get`/`insert`/
+// `contains_key` shape, so `XCDPCoreV1`/`V2`/`V3` and the methods on
+// `XCDPCore` don't need to know which one they're holding — only the three
+// `new_*_map` constructors below differ per cfg.
+#[cfg(not(test))]
+type EventsMap = LookupMap<String, XCDPSendMessage>;
+#[cfg(test)]
+type EventsMap = HashMap<String, XCDPSendMessage>;
+
+#[cfg(not(test))]
+type NoncesMap = LookupMap<String, u64>;
+#[cfg(test)]
+type NoncesMap = HashMap<String, u64>;
+
+#[cfg(not(test))]
+type PayloadsMap = LookupMap<String, Payload>;
+#[cfg(test)]
+type PayloadsMap = HashMap<String, Payload>;
+
+#[cfg(not(test))]
+fn new_events_map() -> EventsMap {
+    LookupMap::new(STORAGE_EVENTS_KEY.to_vec())
+}
+#[cfg(test)]
+fn new_events_map() -> EventsMap {
+    EventsMap::new()
+}
+
+#[cfg(not(test))]
+fn new_nonces_map() -> NoncesMap {
+    LookupMap::new(STORAGE_NONCES_KEY.to_vec())
+}
+#[cfg(test)]
+fn new_nonces_map() -> NoncesMap {
+    NoncesMap::new()
+}
+
+#[cfg(not(test))]
+fn new_payloads_map() -> PayloadsMap {
+    LookupMap::new(STORAGE_PAYLOADS_KEY.to_vec())
+}
+#[cfg(test)]
+fn new_payloads_map() -> PayloadsMap {
+    PayloadsMap::new()
+}
+
+// The original contract layout. Kept around so storage written before the
+// nonce map was introduced can still be read back and migrated forward.
 #[derive(BorshSerialize, BorshDeserialize)]
-pub struct XCDPCore {
-    events: LookupMap<String, XCDPSendMessage>,
+pub struct XCDPCoreV1 {
+    events: EventsMap,
     total_events: u64,
 }
 
-// Default constructor for the contract
-impl Default for XCDPCore {
+impl Default for XCDPCoreV1 {
     fn default() -> Self {
         Self {
-            events: LookupMap::new(STORAGE_EVENTS_KEY.to_vec()),
+            events: new_events_map(),
             total_events: u64::default(),
         }
     }
 }
 
+// Contract layout introduced to carry the per-network nonce map.
+#[derive(BorshSerialize, BorshDeserialize)]
+pub struct XCDPCoreV2 {
+    events: EventsMap,
+    total_events: u64,
+    nonces: NoncesMap,
+}
+
+impl Default for XCDPCoreV2 {
+    fn default() -> Self {
+        Self {
+            events: new_events_map(),
+            total_events: u64::default(),
+            nonces: new_nonces_map(),
+        }
+    }
+}
+
+// V1 storage keeps its own `events` key prefix, so entries written before
+// this migration was introduced stay reachable under the upgraded layout.
+impl From<XCDPCoreV1> for XCDPCoreV2 {
+    fn from(v1: XCDPCoreV1) -> Self {
+        Self {
+            events: v1.events,
+            total_events: v1.total_events,
+            nonces: new_nonces_map(),
+        }
+    }
+}
+
+// Current contract layout: adds the outbound payload queue relayers poll
+// for messages originated by `send_message`.
+#[derive(BorshSerialize, BorshDeserialize)]
+pub struct XCDPCoreV3 {
+    events: EventsMap,
+    total_events: u64,
+    nonces: NoncesMap,
+    payloads: PayloadsMap,
+}
+
+impl Default for XCDPCoreV3 {
+    fn default() -> Self {
+        Self {
+            events: new_events_map(),
+            total_events: u64::default(),
+            nonces: new_nonces_map(),
+            payloads: new_payloads_map(),
+        }
+    }
+}
+
+impl From<XCDPCoreV2> for XCDPCoreV3 {
+    fn from(v2: XCDPCoreV2) -> Self {
+        Self {
+            events: v2.events,
+            total_events: v2.total_events,
+            nonces: v2.nonces,
+            payloads: new_payloads_map(),
+        }
+    }
+}
+
+// Alias for whichever layout is current. Bumping the contract layout means
+// adding a new `XCDPCoreVN`, a `From<XCDPCoreV(N-1)>` impl, a
+// `VersionedXCDPCore` variant, a `migrate` arm, bumping
+// `CURRENT_STORAGE_DISCRIMINANT` to the new variant's index, and repointing
+// this alias — the `XCDPCore` inherent impl below never has to move.
+pub type XCDPCore = XCDPCoreV3;
+
+// Tagged union of every on-disk contract layout. The Borsh discriminant is
+// the variant's index (a single leading byte), so `load_with()` can tell
+// which layout it is looking at before deserializing the rest of the bytes.
+#[derive(BorshSerialize, BorshDeserialize)]
+enum VersionedXCDPCore {
+    V1(XCDPCoreV1),
+    V2(XCDPCoreV2),
+    V3(XCDPCoreV3),
+}
+
+// Discriminant `save_with` tags the latest layout with; must match
+// `VersionedXCDPCore`'s index for the variant wrapping `XCDPCore` (V3 = 2).
+const CURRENT_STORAGE_DISCRIMINANT: u8 = 2;
+
+impl VersionedXCDPCore {
+    // Upgrades whatever variant was read from storage to the latest
+    // in-memory layout. Returns whether a migration actually happened, so
+    // callers only pay for a re-save when the layout changed.
+    fn migrate(self) -> (XCDPCore, bool) {
+        match self {
+            VersionedXCDPCore::V1(v1) => (XCDPCore::from(XCDPCoreV2::from(v1)), true),
+            VersionedXCDPCore::V2(v2) => (XCDPCore::from(v2), true),
+            VersionedXCDPCore::V3(v3) => (v3, false),
+        }
+    }
+}
+
 impl XCDPCore {
-    // Function to load existing contract data from storage
-    fn load() -> Self {
-        match storage_read(STORAGE_CONTRACT_KEY) {
-            Some(bytes) => match Self::try_from_slice(&bytes) {
-                Ok(contract) => contract,
-                Err(_) => panic!("Unable to parse contract bytes"),
-            },
+    // Loads existing contract data from storage, parameterized over
+    // `StorageIO` so it can be exercised against an in-memory store in
+    // tests. Production callers go through this via `save_event_data`.
+    fn load_with<S: StorageIO>(io: &mut S) -> Self {
+        match io.read(STORAGE_CONTRACT_KEY) {
+            Some(bytes) => {
+                // No hand-maintained "known discriminants" list here:
+                // `VersionedXCDPCore::try_from_slice` is the single source of
+                // truth for which discriminants are valid, so an unrecognized
+                // one surfaces as a Borsh error rather than silently passing
+                // a stale allow-list.
+                let versioned = match VersionedXCDPCore::try_from_slice(&bytes) {
+                    Ok(versioned) => versioned,
+                    Err(err) => panic!("Unable to parse contract bytes: {}", err),
+                };
+
+                let (mut contract, migrated) = versioned.migrate();
+                if migrated {
+                    contract.save_with(io);
+                }
+                contract
+            }
             None => panic!("The contract isn't initialized"),
         }
     }
 
-    // Function to save contract state to storage
+    // Function to save contract state to storage.
     fn save(&mut self) {
-        match self.try_to_vec() {
-            Ok(encoded_contract) => {
-                storage_write(STORAGE_CONTRACT_KEY, &encoded_contract);
+        self.save_with(&mut L1xStorageIO)
+    }
+
+    // Core of `save()`, parameterized over `StorageIO`. Always writes the
+    // latest layout, tagged with its discriminant byte so `load_with()` can
+    // recognize it on the next read.
+    fn save_with<S: StorageIO>(&mut self, io: &mut S) {
+        let mut encoded_contract = Vec::new();
+        match BorshSerialize::serialize(&CURRENT_STORAGE_DISCRIMINANT, &mut encoded_contract)
+            .and_then(|_| self.serialize(&mut encoded_contract))
+        {
+            Ok(()) => {
+                io.write(STORAGE_CONTRACT_KEY, &encoded_contract);
                 log::info!("Saved event data successfully");
             }
             Err(_) => panic!("Unable to save contract"),
@@ -95,43 +432,59 @@ impl XCDPCore {
 
     // Handler to process incoming events and save the decoded data
     pub fn save_event_data(event_data: Vec<u8>, global_tx_id: String) {
+        Self::save_event_data_with(&mut L1xStorageIO, event_data, global_tx_id)
+    }
+
+    // Core of `save_event_data`, parameterized over `StorageIO` so the full
+    // pipeline (load, dedup check, base64 decode, ABI decode, key
+    // composition, persist) can be exercised in a plain `cargo test` against
+    // an in-memory store instead of the on-chain runtime.
+    fn save_event_data_with<S: StorageIO>(io: &mut S, event_data: Vec<u8>, global_tx_id: String) {
+        #[cfg(not(test))]
         l1x_sdk::msg(&format!(
             "********************global tx id {} **************",
             global_tx_id
         ));
 
-        let mut contract = Self::load();
+        let mut contract = Self::load_with(io);
 
         log::info!("Received event data!!!");
         assert!(!global_tx_id.is_empty(), "global_tx_id cannot be empty");
         assert!(!event_data.is_empty(), "event_data cannot be empty");
-        assert!(
-            !contract.events.contains_key(&global_tx_id),
-            "event is saved already"
-        );
 
-        let event_data = match base64::decode(&event_data) {
-            Ok(data) => data,
-            Err(_) => panic!("Can't decode base64 event_data"),
+        let log = match decode_log_bytes(&event_data) {
+            Ok(log) => log,
+            Err(err) => panic!("{}", err),
         };
 
-        let log: Log = serde_json::from_slice(&event_data).expect("Can't deserialize Log object");
-
+        #[cfg(not(test))]
         l1x_sdk::msg(&format!("{:#?}", log));
-        let event_id = log.topics[0].to_string();
-        let decoded_event_data = decode(
-            &[ParamType::String],
-            &log.data.0,
-        )
-        .unwrap();
-
-        let event = XCDPSendMessageSolidity {
-            message: decoded_event_data[0].clone().into_string().unwrap(),
+
+        let (topic0, decoded_event) = match decode_event(&log) {
+            Ok(decoded) => decoded,
+            Err(err) => panic!("Unable to decode event: {}", err),
         };
+        let event_id = topic0.to_string();
+        let DecodedEvent::SendMessage(event) = decoded_event;
 
-        contract.save_message_event(global_tx_id, event_id, event.into(), "destination_network_placeholder".to_string(), [0u8; 32]);
+        // Dedup has to use the same key storage does: `global_tx_id` alone
+        // is never an actual map key (see `to_key`/`save_message_event`), so
+        // checking it bare let a replayed `global_tx_id` through silently.
+        let dedup_key = Self::to_key(global_tx_id.clone(), event_id.clone());
+        assert!(
+            !contract.events.contains_key(&dedup_key),
+            "event is saved already"
+        );
+
+        contract.save_message_event(
+            global_tx_id,
+            event_id,
+            event,
+            "destination_network_placeholder".to_string(),
+            [0u8; 32],
+        );
 
-        contract.save()
+        contract.save_with(io)
     }
 
     // Function to combine parts of an event into a single storage key
@@ -140,7 +493,14 @@ impl XCDPCore {
     }
 
     // Function to save a message event
-    pub fn save_message_event(&mut self, global_tx_id: String, event_id: String, event: XCDPSendMessage, destination_network: String, destination_smart_contract_address: [u8; 32]) {
+    pub fn save_message_event(
+        &mut self,
+        global_tx_id: String,
+        event_id: String,
+        event: XCDPSendMessage,
+        destination_network: String,
+        destination_smart_contract_address: [u8; 32],
+    ) {
         let key = Self::to_key(global_tx_id.clone(), event_id.clone());
         self.events.insert(key, event.clone());
         self.total_events += 1;
@@ -153,4 +513,259 @@ impl XCDPCore {
             destination_smart_contract_address
         );
     }
+
+    // Originates a cross-chain message: ABI-encodes it the way the
+    // Solidity side expects, queues the resulting `Payload` for the
+    // relayer to pick up, and emits `XTalkMessageInitiated` so the relayer
+    // can observe it.
+    pub fn send_message(
+        &mut self,
+        message: String,
+        destination_network: String,
+        destination_contract_address: [u8; 32],
+    ) {
+        assert!(!message.is_empty(), "message cannot be empty");
+        assert!(
+            !destination_network.is_empty(),
+            "destination_network cannot be empty"
+        );
+
+        let data = encode(&[
+            Token::Bytes(message.clone().into_bytes()),
+            Token::String(destination_network.clone()),
+            Token::FixedBytes(destination_contract_address.to_vec()),
+        ]);
+
+        let payload = Payload {
+            data,
+            destination_network: destination_network.clone(),
+            destination_contract_address,
+        };
+
+        let nonce = self.nonces.get(&destination_network).copied().unwrap_or(0);
+        let payload_key = Self::to_key(destination_network.clone(), nonce.to_string());
+        self.payloads.insert(payload_key.clone(), payload);
+        self.nonces.insert(destination_network.clone(), nonce + 1);
+
+        #[cfg(not(test))]
+        {
+            let event = XTalkMessageInitiated {
+                message: message.into_bytes(),
+                destination_network: destination_network.clone(),
+                destination_smart_contract_address: destination_contract_address,
+            };
+            l1x_sdk::msg(&format!(
+                "XTalkMessageInitiated: {}",
+                serde_json::to_string(&event).unwrap_or_else(|_| format!("{:?}", event))
+            ));
+        }
+        log::info!(
+            "Queued outbound payload {} for destination_network: {}, destination_contract_address: {:?}",
+            payload_key,
+            destination_network,
+            destination_contract_address
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ethers::utils::keccak256;
+    use std::collections::HashMap as StdHashMap;
+
+    // Canonical Solidity event signature `XCDP_SEND_MESSAGE_TOPIC0` is
+    // supposed to be the hash of. Only needed to regenerate/drift-check that
+    // constant (`topic0_matches_its_signature` below); `event_registry`
+    // itself never hashes this at runtime. Scoped to tests only — in a
+    // production build `keccak256`/this const had no other caller and
+    // tripped `unused_imports`/`dead_code`.
+    const XCDP_SEND_MESSAGE_SIGNATURE: &str = "XCDPSendMessage(string)";
+
+    // In-memory `StorageIO`, so the migration/decode/key logic above can be
+    // exercised in a plain `cargo test` without the L1X runtime.
+    #[derive(Default)]
+    struct InMemoryStorageIO {
+        data: StdHashMap<Vec<u8>, Vec<u8>>,
+    }
+
+    impl StorageIO for InMemoryStorageIO {
+        fn read(&self, key: &[u8]) -> Option<Vec<u8>> {
+            self.data.get(key).cloned()
+        }
+
+        fn write(&mut self, key: &[u8], value: &[u8]) {
+            self.data.insert(key.to_vec(), value.to_vec());
+        }
+    }
+
+    fn crafted_send_message_log(message: &str) -> Log {
+        let data = encode(&[Token::String(message.to_string())]);
+        Log {
+            topics: vec![H256::from(XCDP_SEND_MESSAGE_TOPIC0)],
+            data: data.into(),
+            ..Default::default()
+        }
+    }
+
+    // Base64-encodes a crafted `Log` the same way a relayer would, ready to
+    // feed straight into `save_event_data_with`/`decode_log_bytes`.
+    fn crafted_send_message_event_data(message: &str) -> Vec<u8> {
+        let log_json =
+            serde_json::to_vec(&crafted_send_message_log(message)).expect("serialize crafted Log");
+        base64::encode(log_json).into_bytes()
+    }
+
+    // Guards against the hardcoded `XCDP_SEND_MESSAGE_TOPIC0` drifting from
+    // the canonical signature it's supposed to be the hash of.
+    #[test]
+    fn topic0_matches_its_signature() {
+        assert_eq!(
+            XCDP_SEND_MESSAGE_TOPIC0,
+            keccak256(XCDP_SEND_MESSAGE_SIGNATURE.as_bytes())
+        );
+    }
+
+    #[test]
+    fn load_migrates_v1_storage_to_latest_and_resaves() {
+        let mut io = InMemoryStorageIO::default();
+        let v1 = VersionedXCDPCore::V1(XCDPCoreV1::default());
+        io.write(
+            STORAGE_CONTRACT_KEY,
+            &v1.try_to_vec().expect("serialize VersionedXCDPCore::V1"),
+        );
+
+        let contract = XCDPCore::load_with(&mut io);
+        assert_eq!(contract.total_events, 0);
+
+        let resaved = io
+            .read(STORAGE_CONTRACT_KEY)
+            .expect("load_with should persist the migrated layout");
+        assert_eq!(
+            resaved.first(),
+            Some(&CURRENT_STORAGE_DISCRIMINANT),
+            "resaved bytes should be tagged with the latest discriminant"
+        );
+    }
+
+    #[test]
+    fn load_does_not_resave_when_already_latest() {
+        let mut io = InMemoryStorageIO::default();
+        let v3 = VersionedXCDPCore::V3(XCDPCore::default());
+        let original_bytes = v3.try_to_vec().expect("serialize VersionedXCDPCore::V3");
+        io.write(STORAGE_CONTRACT_KEY, &original_bytes);
+
+        let _contract = XCDPCore::load_with(&mut io);
+        assert_eq!(io.read(STORAGE_CONTRACT_KEY).unwrap(), original_bytes);
+    }
+
+    #[test]
+    fn to_key_joins_id_and_event_type() {
+        assert_eq!(
+            XCDPCore::to_key("tx1".to_string(), "evt".to_string()),
+            "tx1-evt"
+        );
+    }
+
+    #[test]
+    fn decode_log_bytes_rejects_invalid_base64() {
+        let err = decode_log_bytes(b"not valid base64!!").unwrap_err();
+        assert!(err.contains("base64"));
+    }
+
+    #[test]
+    fn decode_log_bytes_roundtrips_a_crafted_send_message_log() {
+        let encoded = crafted_send_message_event_data("hello world");
+
+        let decoded_log = decode_log_bytes(&encoded).expect("decode crafted base64 Log blob");
+        let (_, decoded_event) = decode_event(&decoded_log).expect("known signature decodes");
+        let DecodedEvent::SendMessage(event) = decoded_event;
+        assert_eq!(event.message, "hello world");
+    }
+
+    #[test]
+    fn decode_event_rejects_unknown_signature() {
+        let log = Log {
+            topics: vec![H256::zero()],
+            data: encode(&[Token::String("hi".to_string())]).into(),
+            ..Default::default()
+        };
+        assert!(matches!(
+            decode_event(&log),
+            Err(EventDecodeError::UnknownSignature(_))
+        ));
+    }
+
+    #[test]
+    fn decode_event_rejects_log_with_no_topics() {
+        let log = Log::default();
+        assert!(matches!(
+            decode_event(&log),
+            Err(EventDecodeError::MissingTopic0)
+        ));
+    }
+
+    #[test]
+    fn send_message_queues_payload_and_increments_nonce() {
+        let mut contract = XCDPCore::default();
+
+        contract.send_message("hello".to_string(), "ethereum".to_string(), [7u8; 32]);
+
+        let first_key = XCDPCore::to_key("ethereum".to_string(), "0".to_string());
+        let payload = contract
+            .payloads
+            .get(&first_key)
+            .expect("payload queued under nonce 0");
+        assert_eq!(payload.destination_network, "ethereum");
+        assert_eq!(payload.destination_contract_address, [7u8; 32]);
+        assert_eq!(
+            *contract.nonces.get(&"ethereum".to_string()).unwrap(),
+            1,
+            "nonce should advance past the first queued payload"
+        );
+
+        contract.send_message("world".to_string(), "ethereum".to_string(), [7u8; 32]);
+
+        let second_key = XCDPCore::to_key("ethereum".to_string(), "1".to_string());
+        assert!(
+            contract.payloads.get(&second_key).is_some(),
+            "second payload should be queued under the next nonce"
+        );
+        assert_eq!(
+            *contract.nonces.get(&"ethereum".to_string()).unwrap(),
+            2,
+            "nonce should advance again for the same destination network"
+        );
+    }
+
+    #[test]
+    fn save_event_data_with_decodes_and_persists_the_event() {
+        let mut io = InMemoryStorageIO::default();
+        XCDPCore::default().save_with(&mut io);
+
+        let event_data = crafted_send_message_event_data("hello world");
+        XCDPCore::save_event_data_with(&mut io, event_data, "tx1".to_string());
+
+        let contract = XCDPCore::load_with(&mut io);
+        assert_eq!(contract.total_events, 1);
+
+        let event_id = H256::from(XCDP_SEND_MESSAGE_TOPIC0).to_string();
+        let key = XCDPCore::to_key("tx1".to_string(), event_id);
+        let stored = contract
+            .events
+            .get(&key)
+            .expect("event stored under the composed global_tx_id-event_id key");
+        assert_eq!(stored.message, "hello world");
+    }
+
+    #[test]
+    #[should_panic(expected = "event is saved already")]
+    fn save_event_data_with_rejects_a_replayed_global_tx_id() {
+        let mut io = InMemoryStorageIO::default();
+        XCDPCore::default().save_with(&mut io);
+
+        let event_data = crafted_send_message_event_data("hello world");
+        XCDPCore::save_event_data_with(&mut io, event_data.clone(), "tx1".to_string());
+        XCDPCore::save_event_data_with(&mut io, event_data, "tx1".to_string());
+    }
 }